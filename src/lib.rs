@@ -0,0 +1,3 @@
+pub mod list;
+pub mod tree;
+pub mod tree_map;
@@ -117,3 +117,30 @@ impl<T> From<ListNode<T>> for Vec<T> {
         vec
     }
 }
+
+// Implement a lazy borrowing iterator for `ListNode<T>`
+pub struct ListIter<'a, T> {
+    current: &'a ListNode<T>,
+}
+
+impl<'a, T> Iterator for ListIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.current {
+            ListNode::Nil => None,
+            ListNode::Cons(value, next) => {
+                self.current = next;
+                Some(value)
+            }
+        }
+    }
+}
+
+impl<T> ListNode<T> {
+    /// Returns a lazy iterator over the list's elements, borrowing from `self` instead of
+    /// consuming it.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        ListIter { current: self }
+    }
+}
@@ -1,18 +1,39 @@
 #[allow(unused_imports)]
-use std::{cmp::Ord, mem};
+use std::{cmp::Ord, fmt::Display, mem};
 
 #[derive(Clone, Debug)]
 pub enum TreeNode<T: Ord> {
     Leaf,
-    Node(T, Box<TreeNode<T>>, Box<TreeNode<T>>),
+    Node(T, Box<TreeNode<T>>, Box<TreeNode<T>>, usize, usize),
 }
 
 // Provided functions
 impl<T: Ord> TreeNode<T> {
+    /// Returns the cached height of the subtree in O(1)
     pub fn height(&self) -> usize {
         match self {
             TreeNode::Leaf => 0,
-            TreeNode::Node(_, left, right) => 1 + std::cmp::max(left.height(), right.height()),
+            TreeNode::Node(_, _, _, height, _) => *height,
+        }
+    }
+
+    /// Returns the cached number of nodes in the subtree in O(1)
+    pub fn size(&self) -> usize {
+        match self {
+            TreeNode::Leaf => 0,
+            TreeNode::Node(_, _, _, _, size) => *size,
+        }
+    }
+
+    /// Recomputes this node's cached height and size from its children's (already-cached)
+    /// metadata.
+    ///
+    /// Must be called after any change to `left` or `right` so the invariant that a node's
+    /// stored height and size are always exact holds once the modification returns.
+    pub(crate) fn update_metadata(&mut self) {
+        if let TreeNode::Node(_, left, right, height, size) = self {
+            *height = 1 + std::cmp::max(left.height(), right.height());
+            *size = 1 + left.size() + right.size();
         }
     }
 
@@ -21,7 +42,7 @@ impl<T: Ord> TreeNode<T> {
         fn is_bst_helper<T: Ord>(tree: &TreeNode<T>, min: Option<&T>, max: Option<&T>) -> bool {
             match tree {
                 TreeNode::Leaf => true,
-                TreeNode::Node(value, left, right) => {
+                TreeNode::Node(value, left, right, _, _) => {
                     match min {
                         Some(min) => {
                             if value <= min {
@@ -49,7 +70,7 @@ impl<T: Ord> TreeNode<T> {
     pub fn is_balanced(&self) -> bool {
         match self {
             TreeNode::Leaf => true,
-            TreeNode::Node(_, left, right) => {
+            TreeNode::Node(_, left, right, _, _) => {
                 let left_height = left.height();
                 let right_height = right.height();
                 let diff = (left_height as i32 - right_height as i32).abs();
@@ -68,7 +89,9 @@ impl<T: Ord> TreeNode<T> {
 impl<T: Ord> TreeNode<T> {
     /// Creates a new `TreeNode<T>` with value `value` and children `left` and `right`
     pub fn node(value: T, left: TreeNode<T>, right: TreeNode<T>) -> TreeNode<T> {
-        TreeNode::Node(value, Box::new(left), Box::new(right))
+        let height = 1 + std::cmp::max(left.height(), right.height());
+        let size = 1 + left.size() + right.size();
+        TreeNode::Node(value, Box::new(left), Box::new(right), height, size)
     }
 
     /// Creates a new `TreeNode<T>` with no children
@@ -83,9 +106,10 @@ impl<T: Ord> TreeNode<T> {
     pub fn insert(&mut self, value: T) {
         match self {
             TreeNode::Leaf => {
-                *self = TreeNode::Node(value, Box::new(TreeNode::Leaf), Box::new(TreeNode::Leaf));
+                *self = TreeNode::node(value, TreeNode::Leaf, TreeNode::Leaf);
+                return;
             }
-            TreeNode::Node(current, left, right) => {
+            TreeNode::Node(current, left, right, _, _) => {
                 if *current < value {
                     right.insert(value);
                 } else if *current > value {
@@ -93,51 +117,60 @@ impl<T: Ord> TreeNode<T> {
                 }
             }
         }
-        if !self.is_balanced() {
+        self.update_metadata();
+        if self.balance_factor().abs() > 1 {
             self.rebalance();
         }
     }
 
     /// Computes the balance factor of the tree (the difference between the height of the left and right subtrees)
-    fn balance_factor(&self) -> i32 {
+    pub(crate) fn balance_factor(&self) -> i32 {
         match self {
             TreeNode::Leaf => 0,
-            TreeNode::Node(_, left, right) => left.height() as i32 - right.height() as i32,
+            TreeNode::Node(_, left, right, _, _) => left.height() as i32 - right.height() as i32,
         }
     }
 
     /// Performs a left rotation on the tree
     pub fn left_rotate(&mut self) {
-        if let TreeNode::Node(value, left, right) = self {
-            if let TreeNode::Node(rvalue, rleft, rright) = *mem::take(right) {
-                **left = TreeNode::Node(mem::replace(value, rvalue), mem::take(left), rleft);
+        if let TreeNode::Node(value, left, right, _, _) = self {
+            if let TreeNode::Node(rvalue, rleft, rright, _, _) = *mem::take(right) {
+                let mut new_left =
+                    TreeNode::Node(mem::replace(value, rvalue), mem::take(left), rleft, 0, 0);
+                new_left.update_metadata();
+                **left = new_left;
                 *right = rright;
             }
         }
+        self.update_metadata();
     }
     /// Performs a right rotation on the tree
     pub fn right_rotate(&mut self) {
-        if let TreeNode::Node(value, left, right) = self {
-            if let TreeNode::Node(lvalue, lleft, lright) = *mem::take(left) {
-                **right = TreeNode::Node(mem::replace(value, lvalue), lright, mem::take(right));
+        if let TreeNode::Node(value, left, right, _, _) = self {
+            if let TreeNode::Node(lvalue, lleft, lright, _, _) = *mem::take(left) {
+                let mut new_right =
+                    TreeNode::Node(mem::replace(value, lvalue), lright, mem::take(right), 0, 0);
+                new_right.update_metadata();
+                **right = new_right;
                 *left = lleft;
             }
         }
+        self.update_metadata();
     }
 
     /// Rebalances the tree using either a single or double rotation, as specified in the AVL tree
     /// rebalancing algorithm.
-    fn rebalance(&mut self) {
+    pub(crate) fn rebalance(&mut self) {
         let bf = self.balance_factor();
         if bf > 1 {
-            if let TreeNode::Node(v, l, r) = self {
+            if let TreeNode::Node(v, l, r, _, _) = self {
                 if l.balance_factor() < 0 {
                     l.left_rotate();
                 }
             }
             self.right_rotate();
         } else if bf < -1 {
-            if let TreeNode::Node(v, l, r) = self {
+            if let TreeNode::Node(v, l, r, _, _) = self {
                 if r.balance_factor() > 0 {
                     r.right_rotate();
                 }
@@ -145,6 +178,109 @@ impl<T: Ord> TreeNode<T> {
             self.left_rotate();
         }
     }
+
+    /// Removes the minimum value from the subtree, splicing its right child up in its place, and
+    /// returns the removed value. Panics if called on a `Leaf`.
+    pub(crate) fn take_min(&mut self) -> T {
+        if let TreeNode::Node(_, left, _, _, _) = self {
+            if matches!(**left, TreeNode::Leaf) {
+                // fall through to splice `self` out below
+            } else {
+                let min = left.take_min();
+                self.update_metadata();
+                if self.balance_factor().abs() > 1 {
+                    self.rebalance();
+                }
+                return min;
+            }
+        } else {
+            unreachable!("take_min called on an empty subtree");
+        }
+        match mem::take(self) {
+            TreeNode::Node(value, _, right, _, _) => {
+                *self = *right;
+                value
+            }
+            TreeNode::Leaf => unreachable!(),
+        }
+    }
+
+    /// Removes `value` from the tree if present, re-balancing every ancestor on the way back up.
+    ///
+    /// Returns whether a matching value was found and removed.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let removed = match self {
+            TreeNode::Leaf => false,
+            TreeNode::Node(current, left, right, _, _) => {
+                if value < current {
+                    left.remove(value)
+                } else if value > current {
+                    right.remove(value)
+                } else {
+                    match (&**left, &**right) {
+                        (TreeNode::Leaf, TreeNode::Leaf) => {
+                            *self = TreeNode::Leaf;
+                            return true;
+                        }
+                        (TreeNode::Leaf, _) => {
+                            *self = mem::take(right);
+                            return true;
+                        }
+                        (_, TreeNode::Leaf) => {
+                            *self = mem::take(left);
+                            return true;
+                        }
+                        _ => {
+                            *current = right.take_min();
+                            true
+                        }
+                    }
+                }
+            }
+        };
+        if removed {
+            self.update_metadata();
+            if self.balance_factor().abs() > 1 {
+                self.rebalance();
+            }
+        }
+        removed
+    }
+
+    /// Returns the `k`-th smallest element (0-indexed) in the tree, or `None` if `k` is out of
+    /// bounds. Runs in O(log n) using the cached subtree sizes.
+    pub fn select(&self, k: usize) -> Option<&T> {
+        match self {
+            TreeNode::Leaf => None,
+            TreeNode::Node(value, left, right, _, _) => {
+                let left_size = left.size();
+                if k < left_size {
+                    left.select(k)
+                } else if k == left_size {
+                    Some(value)
+                } else {
+                    right.select(k - left_size - 1)
+                }
+            }
+        }
+    }
+
+    /// Returns the number of stored elements strictly less than `value`. Runs in O(log n) using
+    /// the cached subtree sizes.
+    pub fn rank(&self, value: &T) -> usize {
+        match self {
+            TreeNode::Leaf => 0,
+            TreeNode::Node(current, left, right, _, _) => {
+                if value < current {
+                    left.rank(value)
+                } else if value > current {
+                    left.size() + 1 + right.rank(value)
+                } else {
+                    left.size()
+                }
+            }
+        }
+    }
 }
 
 // Implement `Default` for `TreeNode<T>`
@@ -159,7 +295,7 @@ impl<T: Ord + PartialEq> PartialEq for TreeNode<T> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (TreeNode::Leaf, TreeNode::Leaf) => true,
-            (TreeNode::Node(v1, n1, n2), TreeNode::Node(v2, n3, n4)) => {
+            (TreeNode::Node(v1, n1, n2, _, _), TreeNode::Node(v2, n3, n4, _, _)) => {
                 v1 == v2 && n1 == n3 && n2 == n4
             }
             _ => false,
@@ -186,7 +322,7 @@ impl<T: Ord> From<TreeNode<T>> for Vec<T> {
     fn from(tree_node: TreeNode<T>) -> Vec<T> {
         match tree_node {
             TreeNode::Leaf => Vec::new(),
-            TreeNode::Node(value, left, right) => {
+            TreeNode::Node(value, left, right, _, _) => {
                 let mut vec = Self::from(*left);
                 vec.push(value);
                 vec.append(&mut Self::from(*right));
@@ -195,3 +331,129 @@ impl<T: Ord> From<TreeNode<T>> for Vec<T> {
         }
     }
 }
+
+/// A lazy in-order iterator over `&T`, backed by an explicit stack of node references so no
+/// allocation beyond the stack itself is needed.
+pub struct TreeIter<'a, T: Ord> {
+    stack: Vec<&'a TreeNode<T>>,
+}
+
+impl<'a, T: Ord> TreeIter<'a, T> {
+    fn new(root: &'a TreeNode<T>) -> Self {
+        let mut iter = TreeIter { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut node: &'a TreeNode<T>) {
+        while let TreeNode::Node(_, left, _, _, _) = node {
+            self.stack.push(node);
+            node = left;
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for TreeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        match node {
+            TreeNode::Node(value, _, right, _, _) => {
+                self.push_left_spine(right);
+                Some(value)
+            }
+            TreeNode::Leaf => None,
+        }
+    }
+}
+
+/// A lazy in-order iterator over owned `T`, backed by an explicit stack of partially-consumed
+/// nodes so no allocation beyond the stack itself is needed.
+pub struct TreeIntoIter<T: Ord> {
+    stack: Vec<TreeNode<T>>,
+}
+
+impl<T: Ord> TreeIntoIter<T> {
+    fn new(root: TreeNode<T>) -> Self {
+        let mut iter = TreeIntoIter { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut node: TreeNode<T>) {
+        while let TreeNode::Node(value, left, right, height, size) = node {
+            node = *left;
+            self.stack
+                .push(TreeNode::Node(value, Box::new(TreeNode::Leaf), right, height, size));
+        }
+    }
+}
+
+impl<T: Ord> Iterator for TreeIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.stack.pop()? {
+            TreeNode::Node(value, _, right, _, _) => {
+                self.push_left_spine(*right);
+                Some(value)
+            }
+            TreeNode::Leaf => None,
+        }
+    }
+}
+
+impl<T: Ord> TreeNode<T> {
+    /// Returns a lazy iterator over the tree's elements in ascending (in-order) order, borrowing
+    /// from `self` instead of consuming it.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        TreeIter::new(self)
+    }
+}
+
+// Implement `IntoIterator` for `TreeNode<T>`
+impl<T: Ord> IntoIterator for TreeNode<T> {
+    type Item = T;
+    type IntoIter = TreeIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        TreeIntoIter::new(self)
+    }
+}
+
+impl<T: Ord + Display> TreeNode<T> {
+    /// Renders the tree sideways using Unicode box-drawing connectors, with the right subtree
+    /// above the root and the left subtree below it, so the output reads like a rotated tree
+    /// diagram.
+    pub fn draw(&self) -> String {
+        let mut output = String::new();
+        self.draw_helper(&mut output, String::new(), false);
+        output
+    }
+
+    /// Emits the right child (with a descending prefix), then this node's value, then the left
+    /// child (with an ascending prefix), carrying the accumulated prefix for the current depth
+    /// and branch side so each node's vertical bar connects to its parent.
+    fn draw_helper(&self, output: &mut String, prefix: String, is_left: bool) {
+        if let TreeNode::Node(value, left, right, _, _) = self {
+            if !matches!(**right, TreeNode::Leaf) {
+                let child_prefix = format!("{}{}", prefix, if is_left { "│   " } else { "    " });
+                right.draw_helper(output, child_prefix, false);
+            }
+            let connector = if is_left { "└── " } else { "┌── " };
+            output.push_str(&format!("{}{}{}\n", prefix, connector, value));
+            if !matches!(**left, TreeNode::Leaf) {
+                let child_prefix = format!("{}{}", prefix, if is_left { "    " } else { "│   " });
+                left.draw_helper(output, child_prefix, true);
+            }
+        }
+    }
+}
+
+// Implement `Display` for `TreeNode<T>`
+impl<T: Ord + Display> Display for TreeNode<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.draw())
+    }
+}
@@ -0,0 +1,152 @@
+use crate::tree::TreeNode;
+use std::{cmp::Ordering, mem};
+
+/// A key/value pair ordered solely by its key, so `TreeNode<Entry<K, V>>` can back a sorted map
+/// while reusing the rotation/rebalance/successor-splice machinery built for `TreeNode<T>`.
+#[derive(Clone, Debug)]
+struct Entry<K, V>(K, V);
+
+impl<K: PartialEq, V> PartialEq for Entry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Eq, V> Eq for Entry<K, V> {}
+
+impl<K: PartialOrd, V> PartialOrd for Entry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<K: Ord, V> Ord for Entry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+/// A sorted map reusing `TreeNode<T>`'s AVL rotation/rebalance machinery, ordering and looking
+/// up entries by `K` while carrying an arbitrary `V` payload.
+#[derive(Clone, Debug)]
+pub struct TreeMap<K: Ord, V>(TreeNode<Entry<K, V>>);
+
+impl<K: Ord, V> TreeMap<K, V> {
+    /// Creates a new, empty `TreeMap<K, V>`
+    pub fn new() -> TreeMap<K, V> {
+        TreeMap::default()
+    }
+
+    /// Returns the cached height of the underlying tree in O(1)
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Returns the cached number of entries in the map in O(1)
+    pub fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    /// Inserts `value` under `key`. If `key` is already present, its value is replaced and the
+    /// tree shape is left untouched; otherwise the entry is inserted and rebalanced via
+    /// `TreeNode::insert`. Returns the previous value stored under `key`, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(mem::replace(existing, value));
+        }
+        self.0.insert(Entry(key, value));
+        None
+    }
+
+    /// Returns a reference to the value stored under `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        Self::get_node(&self.0, key)
+    }
+
+    fn get_node<'a>(node: &'a TreeNode<Entry<K, V>>, key: &K) -> Option<&'a V> {
+        match node {
+            TreeNode::Leaf => None,
+            TreeNode::Node(entry, left, right, _, _) => match key.cmp(&entry.0) {
+                Ordering::Less => Self::get_node(left, key),
+                Ordering::Greater => Self::get_node(right, key),
+                Ordering::Equal => Some(&entry.1),
+            },
+        }
+    }
+
+    /// Returns a mutable reference to the value stored under `key`, if any.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        Self::get_node_mut(&mut self.0, key)
+    }
+
+    fn get_node_mut<'a>(node: &'a mut TreeNode<Entry<K, V>>, key: &K) -> Option<&'a mut V> {
+        match node {
+            TreeNode::Leaf => None,
+            TreeNode::Node(entry, left, right, _, _) => match key.cmp(&entry.0) {
+                Ordering::Less => Self::get_node_mut(left, key),
+                Ordering::Greater => Self::get_node_mut(right, key),
+                Ordering::Equal => Some(&mut entry.1),
+            },
+        }
+    }
+
+    /// Removes the entry stored under `key`, if any, re-balancing every ancestor on the way back
+    /// up via the shared `TreeNode` rotation/rebalance/`take_min` machinery. Returns the removed
+    /// value.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        Self::remove_node(&mut self.0, key)
+    }
+
+    fn remove_node(node: &mut TreeNode<Entry<K, V>>, key: &K) -> Option<V> {
+        let removed = match node {
+            TreeNode::Leaf => None,
+            TreeNode::Node(entry, left, right, _, _) => match key.cmp(&entry.0) {
+                Ordering::Less => Self::remove_node(left, key),
+                Ordering::Greater => Self::remove_node(right, key),
+                Ordering::Equal => match (&**left, &**right) {
+                    (TreeNode::Leaf, TreeNode::Leaf) => match mem::take(node) {
+                        TreeNode::Node(Entry(_, value), ..) => {
+                            *node = TreeNode::Leaf;
+                            return Some(value);
+                        }
+                        TreeNode::Leaf => unreachable!(),
+                    },
+                    (TreeNode::Leaf, _) => match mem::take(node) {
+                        TreeNode::Node(Entry(_, value), _, right, _, _) => {
+                            *node = *right;
+                            return Some(value);
+                        }
+                        TreeNode::Leaf => unreachable!(),
+                    },
+                    (_, TreeNode::Leaf) => match mem::take(node) {
+                        TreeNode::Node(Entry(_, value), left, _, _, _) => {
+                            *node = *left;
+                            return Some(value);
+                        }
+                        TreeNode::Leaf => unreachable!(),
+                    },
+                    _ => {
+                        let Entry(succ_key, succ_value) = right.take_min();
+                        let previous = mem::replace(&mut entry.1, succ_value);
+                        entry.0 = succ_key;
+                        Some(previous)
+                    }
+                },
+            },
+        };
+        if removed.is_some() {
+            node.update_metadata();
+            if node.balance_factor().abs() > 1 {
+                node.rebalance();
+            }
+        }
+        removed
+    }
+}
+
+// Implement `Default` for `TreeMap<K, V>`
+impl<K: Ord, V> Default for TreeMap<K, V> {
+    fn default() -> Self {
+        TreeMap(TreeNode::default())
+    }
+}